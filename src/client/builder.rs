@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::error::Error;
+use crate::transport::Transport;
+
+use super::{Client, RequestHandler};
+
+/// Default per-request timeout used when a [`Client`] is built without an explicit
+/// `default_timeout`, matching the fixed timeout the client used before it was
+/// configurable.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Builds a [`Client`] with configuration beyond a bare transport, such as the
+/// default per-request timeout.
+///
+/// ```ignore
+/// let client = ClientBuilder::new(transport)
+///     .default_timeout(Duration::from_secs(10))
+///     .build();
+/// ```
+pub struct ClientBuilder {
+    transport: Arc<dyn Transport>,
+    default_timeout: Duration,
+    skip_init_gate: bool,
+    request_handlers: HashMap<String, RequestHandler>,
+}
+
+impl ClientBuilder {
+    /// Starts building a client for the given transport, with the default 30-second
+    /// per-request timeout and the initialization gate enabled.
+    pub fn new(transport: Arc<dyn Transport>) -> Self {
+        Self {
+            transport,
+            default_timeout: DEFAULT_TIMEOUT,
+            skip_init_gate: false,
+            request_handlers: HashMap::new(),
+        }
+    }
+
+    /// Registers the handler invoked when the server sends a request for `method`
+    /// (e.g. `sampling/createMessage`, `roots/list`). Equivalent to calling
+    /// `Client::on_request` right after `build()`, but available before the client
+    /// exists. A later call with the same `method` replaces the earlier one.
+    pub fn on_request<F, Fut>(mut self, method: impl Into<String>, handler: F) -> Self
+    where
+        F: Fn(serde_json::Value) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<serde_json::Value, Error>> + Send + 'static,
+    {
+        let handler: RequestHandler = Arc::new(move |params| Box::pin(handler(params)));
+        self.request_handlers.insert(method.into(), handler);
+        self
+    }
+
+    /// Overrides the timeout used by `Client::request` (but not `request_with_timeout`,
+    /// which always takes its own timeout explicitly).
+    pub fn default_timeout(mut self, default_timeout: Duration) -> Self {
+        self.default_timeout = default_timeout;
+        self
+    }
+
+    /// Disables queuing requests until `initialize()` completes. By default, MCP
+    /// requests sent before initialization finishes wait for it rather than racing the
+    /// server; set this if you already manage that ordering yourself.
+    pub fn skip_init_gate(mut self, skip_init_gate: bool) -> Self {
+        self.skip_init_gate = skip_init_gate;
+        self
+    }
+
+    /// Builds the configured client.
+    pub fn build(self) -> Client {
+        Client::with_config(
+            self.transport,
+            self.default_timeout,
+            self.skip_init_gate,
+            self.request_handlers,
+        )
+    }
+}
@@ -1,16 +1,21 @@
+use futures::future::BoxFuture;
 use futures::StreamExt;
+use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
-use tokio::sync::{Mutex, RwLock};
+use std::time::Duration;
+use tokio::sync::{broadcast, oneshot, Mutex, RwLock};
+use tokio_stream::wrappers::BroadcastStream;
 
 use crate::{
-    ReadResourceResult,
     error::{Error, ErrorCode},
-    protocol::{Notification, Request, RequestId},
+    protocol::{Notification, Request, RequestId, Response, RpcError},
     transport::{Message, Transport},
     types::{
         CallToolRequest, CallToolResult, ClientCapabilities, Implementation, InitializeResult,
         ListResourcesResult, ListToolsResult, ServerCapabilities, Tool,
     },
+    ReadResourceResult,
 };
 
 mod builder;
@@ -19,6 +24,102 @@ pub use builder::ClientBuilder;
 #[cfg(test)]
 mod test;
 
+/// A response, still awaiting completion, that a caller of `request()` is holding the other
+/// half of.
+type PendingResponse = oneshot::Sender<Result<serde_json::Value, Error>>;
+
+/// A handler registered via `Client::on_request`/`ClientBuilder::on_request` to answer
+/// a server-initiated JSON-RPC request (e.g. `sampling/createMessage`, `roots/list`).
+type RequestHandler = Arc<
+    dyn Fn(serde_json::Value) -> BoxFuture<'static, Result<serde_json::Value, Error>> + Send + Sync,
+>;
+
+/// A stream of server notifications returned by [`Client::subscribe`] and
+/// [`Client::subscribe_method`]. Slow or absent subscribers simply miss older
+/// notifications (the broadcast channel lags and drops) rather than blocking the
+/// transport's dispatcher task.
+pub type NotificationStream = broadcast::Receiver<Notification>;
+
+/// A single `notifications/progress` update, correlated to the request that attached
+/// its `progressToken`. See [`Client::request_with_progress`].
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ProgressNotification {
+    #[serde(rename = "progressToken")]
+    pub progress_token: serde_json::Value,
+    pub progress: f64,
+    pub total: Option<f64>,
+    pub message: Option<String>,
+}
+
+/// Capacity of the notification broadcast channel. Generous enough that a subscriber
+/// doing a little work between polls won't lag under normal notification volume.
+const NOTIFICATION_CHANNEL_CAPACITY: usize = 256;
+
+/// Removes a request's pending entry and tells the server to stop working on it when
+/// the `request`/`request_with_timeout` future is dropped before a response arrives —
+/// whether from a timeout, or the caller losing a `tokio::select!` race. Call
+/// `resolved()` once a response (or send failure) has actually been handled so the
+/// drop becomes a no-op.
+struct CancelOnDrop {
+    pending: Arc<Mutex<HashMap<RequestId, PendingResponse>>>,
+    transport: Arc<dyn Transport>,
+    id: RequestId,
+    resolved: bool,
+}
+
+impl CancelOnDrop {
+    fn resolved(&mut self) {
+        self.resolved = true;
+    }
+}
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        if self.resolved {
+            return;
+        }
+        let pending = self.pending.clone();
+        let transport = self.transport.clone();
+        let id = self.id.clone();
+        tokio::spawn(async move {
+            // If this entry is already gone, the dispatcher resolved it (the real
+            // response landed) in the same tick we lost the race to observe it — the
+            // request already completed, so there's nothing to tell the server to stop.
+            if pending.lock().await.remove(&id).is_none() {
+                return;
+            }
+            let notification = Notification::new(
+                "notifications/cancelled",
+                Some(serde_json::json!({
+                    "requestId": id,
+                    "reason": "request dropped before a response was received",
+                })),
+            );
+            if let Err(e) = transport.send(Message::Notification(notification)).await {
+                tracing::warn!(?e, "Failed to send notifications/cancelled");
+            }
+        });
+    }
+}
+
+/// Deregisters a `request_with_progress` call's progress-token entry when it goes out
+/// of scope, so a finished or dropped call doesn't leak an entry in `progress_senders`.
+struct ProgressTokenGuard {
+    progress_senders:
+        Arc<Mutex<HashMap<String, tokio::sync::mpsc::UnboundedSender<ProgressNotification>>>>,
+    token: String,
+}
+
+impl Drop for ProgressTokenGuard {
+    fn drop(&mut self) {
+        let progress_senders = self.progress_senders.clone();
+        let token = self.token.clone();
+        tokio::spawn(async move {
+            progress_senders.lock().await.remove(&token);
+        });
+    }
+}
+
 /// The MCP client struct, managing transport, requests, and responses.
 /// This client is suitable for connecting to an MCP-compliant server to
 /// send requests, receive responses, and handle notifications.
@@ -29,40 +130,94 @@ pub struct Client {
     server_capabilities: Arc<RwLock<Option<ServerCapabilities>>>,
     /// Request ID counter to generate unique IDs for each request.
     request_counter: Arc<RwLock<i64>>,
-    /// An MPSC receiver for reading incoming responses from the transport.
-    response_receiver: Arc<Mutex<tokio::sync::mpsc::UnboundedReceiver<Message>>>,
-    /// An MPSC sender for sending responses from the transport handler to this client.
-    #[allow(dead_code)]
-    response_sender: tokio::sync::mpsc::UnboundedSender<Message>,
+    /// In-flight requests, keyed by request ID, waiting on their matching response.
+    /// The dispatcher task (spawned in `with_config`) removes an entry when its response
+    /// arrives; a request also removes its own entry if sending fails or it is dropped.
+    pending: Arc<Mutex<HashMap<RequestId, PendingResponse>>>,
+    /// Broadcasts every `Message::Notification` received from the server so callers
+    /// can observe things like `notifications/resources/updated` or log messages.
+    notification_tx: broadcast::Sender<Notification>,
+    /// Default timeout applied by `request()`; `request_with_timeout()` overrides it
+    /// per call. Configurable via `ClientBuilder::default_timeout`.
+    default_timeout: Duration,
+    /// Set once `initialize()` has stored `server_capabilities` and sent the
+    /// `initialized` notification. `request()` (other than `initialize` itself) waits
+    /// on this so calls made before initialization queue instead of racing the server.
+    initialized: Arc<std::sync::atomic::AtomicBool>,
+    /// Notified after `initialized` is set, to wake anything waiting in `request()`.
+    initialized_notify: Arc<tokio::sync::Notify>,
+    /// When true, `request()` does not wait for initialization. Set via
+    /// `ClientBuilder::skip_init_gate` for callers who manage ordering themselves.
+    skip_init_gate: bool,
+    /// Handlers for server-initiated requests, keyed by method. Populated via
+    /// `ClientBuilder::on_request` and `Client::on_request`.
+    request_handlers: Arc<RwLock<HashMap<String, RequestHandler>>>,
+    /// Channels for in-flight `request_with_progress` calls, keyed by the progress
+    /// token attached to their request's `_meta`.
+    progress_senders:
+        Arc<Mutex<HashMap<String, tokio::sync::mpsc::UnboundedSender<ProgressNotification>>>>,
 }
 
 impl Client {
-    /// Creates a new MCP client with the given transport.
-    /// This does not perform initialization. You typically call `client.initialize(...)` next.
+    /// Creates a new MCP client with the given transport, using the default 30-second
+    /// per-request timeout. This does not perform initialization. You typically call
+    /// `client.initialize(...)` next.
+    ///
+    /// Use [`ClientBuilder`] instead if you need to configure the default timeout or
+    /// the initialization gate.
     pub fn new(transport: Arc<dyn Transport>) -> Self {
-        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        Self::with_config(transport, Duration::from_secs(30), false, HashMap::new())
+    }
+
+    /// Creates a new MCP client with the given transport, default request timeout,
+    /// initialization-gate opt-out, and initial server-request handlers. Used by both
+    /// `new` and `ClientBuilder::build`.
+    pub(crate) fn with_config(
+        transport: Arc<dyn Transport>,
+        default_timeout: Duration,
+        skip_init_gate: bool,
+        request_handlers: HashMap<String, RequestHandler>,
+    ) -> Self {
+        let pending: Arc<Mutex<HashMap<RequestId, PendingResponse>>> =
+            Arc::new(Mutex::new(HashMap::new()));
+        let (notification_tx, _) = broadcast::channel(NOTIFICATION_CHANNEL_CAPACITY);
+        let request_handlers = Arc::new(RwLock::new(request_handlers));
+        let progress_senders = Arc::new(Mutex::new(HashMap::new()));
+
         let client = Self {
             transport: transport.clone(),
             server_capabilities: Arc::new(RwLock::new(None)),
             request_counter: Arc::new(RwLock::new(0)),
-            response_receiver: Arc::new(Mutex::new(rx)),
-            response_sender: tx.clone(),
+            pending: pending.clone(),
+            notification_tx: notification_tx.clone(),
+            default_timeout,
+            initialized: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            initialized_notify: Arc::new(tokio::sync::Notify::new()),
+            skip_init_gate,
+            request_handlers: request_handlers.clone(),
+            progress_senders: progress_senders.clone(),
         };
 
-        // Spawn a task to forward all transport messages into our MPSC channel.
+        // Spawn the dispatcher task: it owns the transport's receive stream for the
+        // lifetime of the client and is the only place that resolves pending requests,
+        // so no caller ever waits behind another caller's response.
         let transport_clone = transport.clone();
-        let tx_clone = tx.clone();
         tokio::spawn(async move {
-            tracing::debug!("Starting response handler task");
+            tracing::debug!("Starting response dispatcher task");
             let mut stream = transport_clone.receive();
             while let Some(result) = stream.next().await {
                 match result {
                     Ok(message) => {
                         tracing::trace!(?message, "Received message from transport");
-                        if tx_clone.send(message).is_err() {
-                            tracing::error!("Failed to forward message - channel closed");
-                            break;
-                        }
+                        Self::dispatch(
+                            &pending,
+                            &notification_tx,
+                            &request_handlers,
+                            &progress_senders,
+                            &transport_clone,
+                            message,
+                        )
+                        .await;
                     }
                     Err(e) => {
                         tracing::error!(?e, "Error receiving message from transport");
@@ -70,13 +225,172 @@ impl Client {
                     }
                 }
             }
-            tracing::debug!("Response handler task terminated");
+
+            // The transport ended; fail every outstanding request rather than leaving
+            // callers waiting on the full timeout.
+            tracing::debug!("Dispatcher task terminated, failing outstanding requests");
+            let mut pending = pending.lock().await;
+            for (_, tx) in pending.drain() {
+                let _ = tx.send(Err(Error::protocol(
+                    ErrorCode::InternalError,
+                    "Connection closed while waiting for response",
+                )));
+            }
         });
 
         tracing::debug!("Created new MCP client");
         client
     }
 
+    /// Routes a single message from the transport: resolves the matching pending request
+    /// on `Message::Response`, publishes `Message::Notification`s to subscribers, or runs
+    /// the registered handler (if any) for a server-initiated `Message::Request`.
+    async fn dispatch(
+        pending: &Arc<Mutex<HashMap<RequestId, PendingResponse>>>,
+        notification_tx: &broadcast::Sender<Notification>,
+        request_handlers: &Arc<RwLock<HashMap<String, RequestHandler>>>,
+        progress_senders: &Arc<
+            Mutex<HashMap<String, tokio::sync::mpsc::UnboundedSender<ProgressNotification>>>,
+        >,
+        transport: &Arc<dyn Transport>,
+        message: Message,
+    ) {
+        match message {
+            Message::Response(response) => {
+                let mut pending = pending.lock().await;
+                let Some(tx) = pending.remove(&response.id) else {
+                    tracing::warn!(
+                        ?response,
+                        "Received response for unknown or already-resolved request"
+                    );
+                    return;
+                };
+                drop(pending);
+
+                let result = if let Some(error) = response.error {
+                    tracing::error!(?error, "Server returned error");
+                    Err(Error::Protocol {
+                        code: error.code.into(),
+                        message: error.message,
+                        data: error.data,
+                    })
+                } else {
+                    response.result.ok_or_else(|| {
+                        Error::protocol(ErrorCode::InternalError, "Response missing result")
+                    })
+                };
+
+                // The caller may have dropped its receiver (e.g. on timeout); that's fine.
+                let _ = tx.send(result);
+            }
+            Message::Notification(notif) => {
+                tracing::debug!(?notif, "Received notification");
+
+                if notif.method == "notifications/progress" {
+                    Self::route_progress(progress_senders, &notif).await;
+                }
+
+                // Subscribers are expected to come and go; no receivers is not an error.
+                let _ = notification_tx.send(notif);
+            }
+            Message::Request(req) => {
+                tracing::debug!(?req, "Received request from server");
+
+                // Run the handler (if any) without blocking the dispatcher loop, so a
+                // slow handler doesn't delay responses/notifications for everyone else.
+                let request_handlers = request_handlers.clone();
+                let transport = transport.clone();
+                tokio::spawn(async move {
+                    let handler = request_handlers.read().await.get(&req.method).cloned();
+
+                    let response = match handler {
+                        Some(handler) => {
+                            let params = req.params.unwrap_or(serde_json::Value::Null);
+                            match handler(params).await {
+                                Ok(result) => Response {
+                                    id: req.id,
+                                    result: Some(result),
+                                    error: None,
+                                },
+                                Err(Error::Protocol {
+                                    code,
+                                    message,
+                                    data,
+                                }) => Response {
+                                    id: req.id,
+                                    result: None,
+                                    error: Some(RpcError {
+                                        code: code.into(),
+                                        message,
+                                        data,
+                                    }),
+                                },
+                                Err(e) => Response {
+                                    id: req.id,
+                                    result: None,
+                                    error: Some(RpcError {
+                                        code: ErrorCode::InternalError.into(),
+                                        message: e.to_string(),
+                                        data: None,
+                                    }),
+                                },
+                            }
+                        }
+                        None => {
+                            tracing::warn!(method = %req.method, "No handler registered for server request");
+                            Response {
+                                id: req.id,
+                                result: None,
+                                error: Some(RpcError {
+                                    code: ErrorCode::MethodNotFound.into(),
+                                    message: format!("No handler registered for '{}'", req.method),
+                                    data: None,
+                                }),
+                            }
+                        }
+                    };
+
+                    if let Err(e) = transport.send(Message::Response(response)).await {
+                        tracing::error!(?e, "Failed to send response to server-initiated request");
+                    }
+                });
+            }
+        }
+    }
+
+    /// Forwards a `notifications/progress` notification to the `request_with_progress`
+    /// caller whose progress token it carries, if any is still registered.
+    async fn route_progress(
+        progress_senders: &Arc<
+            Mutex<HashMap<String, tokio::sync::mpsc::UnboundedSender<ProgressNotification>>>,
+        >,
+        notif: &Notification,
+    ) {
+        let Some(params) = &notif.params else {
+            return;
+        };
+        let progress: ProgressNotification = match serde_json::from_value(params.clone()) {
+            Ok(progress) => progress,
+            Err(e) => {
+                tracing::warn!(?e, "Received malformed notifications/progress");
+                return;
+            }
+        };
+        let token = match &progress.progress_token {
+            serde_json::Value::String(s) => s.clone(),
+            serde_json::Value::Number(n) => n.to_string(),
+            other => {
+                tracing::warn!(?other, "Unexpected progressToken type");
+                return;
+            }
+        };
+
+        let senders = progress_senders.lock().await;
+        if let Some(tx) = senders.get(&token) {
+            let _ = tx.send(progress);
+        }
+    }
+
     /// Initializes the client by sending an "initialize" request containing:
     /// - client implementation info
     /// - client capabilities
@@ -109,85 +423,176 @@ impl Client {
         tracing::debug!("Sending initialized notification");
         self.notify("notifications/initialized", None).await?;
 
+        // Release anything queued in `request()` waiting for initialization.
+        self.initialized
+            .store(true, std::sync::atomic::Ordering::Release);
+        self.initialized_notify.notify_waiters();
+
         tracing::info!("MCP client initialization complete");
         Ok(init_result)
     }
 
+    /// Waits until `initialize()` has completed, unless the gate is disabled via
+    /// `ClientBuilder::skip_init_gate`. Uses the check-notified-check pattern so a
+    /// concurrent `initialize()` can't complete in the gap between the flag check and
+    /// starting to wait.
+    async fn wait_until_initialized(&self) {
+        if self.skip_init_gate {
+            return;
+        }
+        if self.initialized.load(std::sync::atomic::Ordering::Acquire) {
+            return;
+        }
+
+        let notified = self.initialized_notify.notified();
+        if self.initialized.load(std::sync::atomic::Ordering::Acquire) {
+            return;
+        }
+        notified.await;
+    }
+
     /// Sends a request to the server with the given method and optional parameters,
-    /// then waits up to 30 seconds for a matching response.
+    /// then waits for a matching response using the client's default timeout (30
+    /// seconds unless overridden via `ClientBuilder::default_timeout`).
+    ///
+    /// Unlike waiting on a shared receiver, this registers the request in the pending
+    /// map before sending it, so any number of `request()` calls can be in flight at
+    /// once without stealing or blocking on each other's responses.
     ///
     /// # Errors
     ///
     /// Returns an error if the transport fails, the server returns an error,
-    /// or no response is received within 30 seconds.
+    /// or no response is received within the timeout.
     pub async fn request(
         &self,
         method: &str,
         params: Option<serde_json::Value>,
     ) -> Result<serde_json::Value, Error> {
+        self.request_with_timeout(method, params, self.default_timeout)
+            .await
+    }
+
+    /// Like `request`, but with an explicit timeout instead of the client's default.
+    ///
+    /// If the returned future is dropped before completion — on timeout, or because
+    /// the caller lost a `tokio::select!` race — a `notifications/cancelled`
+    /// notification carrying the request's id is sent to the server so it can stop
+    /// working on it.
+    pub async fn request_with_timeout(
+        &self,
+        method: &str,
+        params: Option<serde_json::Value>,
+        timeout: Duration,
+    ) -> Result<serde_json::Value, Error> {
+        // `initialize` itself must bypass the gate, or it would wait on itself forever.
+        if method != "initialize" {
+            self.wait_until_initialized().await;
+        }
+
         // Increment request ID
         let mut counter = self.request_counter.write().await;
         *counter += 1;
         let id = RequestId::Number(*counter);
+        drop(counter);
+
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().await.insert(id.clone(), tx);
+
+        let mut guard = CancelOnDrop {
+            pending: self.pending.clone(),
+            transport: self.transport.clone(),
+            id: id.clone(),
+            resolved: false,
+        };
 
         let request = Request::new(method, params, id.clone());
         tracing::debug!(?request, "Sending MCP request");
 
-        // Send request
-        self.transport.send(Message::Request(request)).await?;
-
-        // Wait for a matching response (by request ID) or a 30s timeout
-        let mut rx = self.response_receiver.lock().await;
-        match tokio::time::timeout(std::time::Duration::from_secs(30), async {
-            while let Some(message) = rx.recv().await {
-                match message {
-                    Message::Response(response) if response.id == id => {
-                        tracing::debug!(?response, "Received matching MCP response");
-                        if let Some(error) = response.error {
-                            tracing::error!(?error, "Server returned error");
-                            return Err(Error::Protocol {
-                                code: error.code.into(),
-                                message: error.message,
-                                data: error.data,
-                            });
-                        }
-                        return response.result.ok_or_else(|| {
-                            Error::protocol(ErrorCode::InternalError, "Response missing result")
-                        });
-                    }
-                    Message::Response(response) => {
-                        tracing::debug!(
-                            ?response,
-                            "Received non-matching response, continuing to wait"
-                        );
-                    }
-                    Message::Notification(notif) => {
-                        tracing::debug!(?notif, "Received notification while waiting for response");
-                    }
-                    Message::Request(req) => {
-                        tracing::debug!(?req, "Received request while waiting for response");
-                    }
-                }
-            }
+        if let Err(e) = self.transport.send(Message::Request(request)).await {
+            self.pending.lock().await.remove(&id);
+            guard.resolved(); // never reached the server, nothing to cancel
+            return Err(e);
+        }
 
-            // Channel closed or no more messages.
-            Err(Error::protocol(
-                ErrorCode::InternalError,
-                "Connection closed while waiting for response",
-            ))
-        })
-        .await
-        {
-            Ok(result) => result,
+        match tokio::time::timeout(timeout, rx).await {
+            Ok(Ok(result)) => {
+                guard.resolved();
+                result
+            }
+            Ok(Err(_)) => {
+                // The sender was dropped without a reply. The dispatcher sends on every
+                // sender it removes, so this is reported as the connection closing.
+                guard.resolved();
+                Err(Error::protocol(
+                    ErrorCode::InternalError,
+                    "Connection closed while waiting for response",
+                ))
+            }
             Err(_) => {
-                tracing::error!("Request to '{}' timed out after 30 seconds", method);
+                tracing::error!("Request to '{}' timed out after {:?}", method, timeout);
+                // Leave `guard` unresolved so its `Drop` fires `notifications/cancelled`.
                 Err(Error::Other(format!(
-                    "Request to '{method}' timed out after 30 seconds"
+                    "Request to '{method}' timed out after {timeout:?}"
                 )))
             }
         }
     }
 
+    /// Like `request`, but attaches a `progressToken` to the request's `_meta` and
+    /// calls `on_progress` for each `notifications/progress` update the server sends
+    /// back correlated to that token, giving live progress for long-running calls
+    /// (e.g. `tools/call`) without polling.
+    pub async fn request_with_progress<F>(
+        &self,
+        method: &str,
+        params: Option<serde_json::Value>,
+        mut on_progress: F,
+    ) -> Result<serde_json::Value, Error>
+    where
+        F: FnMut(ProgressNotification) + Send + 'static,
+    {
+        let mut counter = self.request_counter.write().await;
+        *counter += 1;
+        let token = counter.to_string();
+        drop(counter);
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<ProgressNotification>();
+        self.progress_senders.lock().await.insert(token.clone(), tx);
+        let _guard = ProgressTokenGuard {
+            progress_senders: self.progress_senders.clone(),
+            token: token.clone(),
+        };
+
+        tokio::spawn(async move {
+            while let Some(progress) = rx.recv().await {
+                on_progress(progress);
+            }
+        });
+
+        let params =
+            Self::inject_progress_token(params.unwrap_or_else(|| serde_json::json!({})), &token);
+        self.request(method, Some(params)).await
+    }
+
+    /// Attaches `_meta.progressToken` to a request's params, creating `_meta` if
+    /// absent. Params that aren't a JSON object are returned unchanged (with a
+    /// warning), since MCP request params are always objects.
+    fn inject_progress_token(mut params: serde_json::Value, token: &str) -> serde_json::Value {
+        let Some(obj) = params.as_object_mut() else {
+            tracing::warn!("Request params were not a JSON object; cannot attach progressToken");
+            return params;
+        };
+        let meta = obj
+            .entry("_meta".to_string())
+            .or_insert_with(|| serde_json::json!({}));
+        if let Some(meta_obj) = meta.as_object_mut() {
+            meta_obj.insert("progressToken".to_string(), serde_json::json!(token));
+        } else {
+            tracing::warn!("Request params had a non-object `_meta`; cannot attach progressToken");
+        }
+        params
+    }
+
     /// Sends a notification to the server using the given method and optional parameters.
     /// Notifications do not expect a response from the server.
     pub async fn notify(
@@ -202,6 +607,50 @@ impl Client {
             .await
     }
 
+    /// Registers (or replaces) the handler invoked when the server sends a
+    /// `Message::Request` for `method`, turning this client into a full duplex
+    /// endpoint. Useful for server-initiated flows like `sampling/createMessage` or
+    /// `roots/list`. A method with no registered handler gets back a JSON-RPC
+    /// "method not found" error.
+    pub async fn on_request<F, Fut>(&self, method: impl Into<String>, handler: F)
+    where
+        F: Fn(serde_json::Value) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<serde_json::Value, Error>> + Send + 'static,
+    {
+        let handler: RequestHandler = Arc::new(move |params| Box::pin(handler(params)));
+        self.request_handlers
+            .write()
+            .await
+            .insert(method.into(), handler);
+    }
+
+    /// Subscribes to every notification the server sends. Call this before the
+    /// notification you care about is expected, since it only sees notifications
+    /// broadcast after the subscription is created.
+    pub fn subscribe(&self) -> NotificationStream {
+        self.notification_tx.subscribe()
+    }
+
+    /// Subscribes to notifications whose `method` matches the given string, as a stream
+    /// of `Notification`s. Lagged notifications (the subscriber fell behind) are
+    /// silently skipped rather than surfaced as an error.
+    pub fn subscribe_method(
+        &self,
+        method: impl Into<String>,
+    ) -> impl futures::Stream<Item = Notification> + Send + 'static {
+        let method = method.into();
+        BroadcastStream::new(self.subscribe()).filter_map(move |item| {
+            let method = method.clone();
+            async move {
+                match item {
+                    Ok(notif) if notif.method == method => Some(notif),
+                    Ok(_) => None,
+                    Err(_) => None,
+                }
+            }
+        })
+    }
+
     /// Returns the cached server capabilities if the client has already initialized.
     pub async fn capabilities(&self) -> Option<ServerCapabilities> {
         let caps = self.server_capabilities.read().await.clone();
@@ -0,0 +1,392 @@
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::{Stream, StreamExt};
+use tokio::sync::Mutex;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+
+use super::*;
+use crate::protocol::{Request, Response};
+
+/// An in-memory `Transport` for exercising `Client` without a real connection.
+/// Messages pushed via `push` are delivered to the client's dispatcher as if they
+/// came from the server; everything the client sends is captured in `sent` for
+/// assertions.
+struct FakeTransport {
+    incoming_tx: tokio::sync::mpsc::UnboundedSender<Result<Message, Error>>,
+    incoming_rx: Mutex<Option<tokio::sync::mpsc::UnboundedReceiver<Result<Message, Error>>>>,
+    sent: Mutex<Vec<Message>>,
+}
+
+impl FakeTransport {
+    fn new() -> Arc<Self> {
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        Arc::new(Self {
+            incoming_tx: tx,
+            incoming_rx: Mutex::new(Some(rx)),
+            sent: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Feeds a message to the client as if the server had sent it.
+    fn push(&self, message: Message) {
+        let _ = self.incoming_tx.send(Ok(message));
+    }
+
+    async fn sent_messages(&self) -> Vec<Message> {
+        self.sent.lock().await.clone()
+    }
+}
+
+#[async_trait]
+impl Transport for FakeTransport {
+    fn receive(&self) -> Pin<Box<dyn Stream<Item = Result<Message, Error>> + Send>> {
+        let rx = self
+            .incoming_rx
+            .try_lock()
+            .expect("receive() called concurrently")
+            .take()
+            .expect("receive() called more than once, same as the dispatcher would");
+        Box::pin(UnboundedReceiverStream::new(rx))
+    }
+
+    async fn send(&self, message: Message) -> Result<(), Error> {
+        self.sent.lock().await.push(message);
+        Ok(())
+    }
+
+    async fn close(&self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+fn ok_response(id: RequestId, result: serde_json::Value) -> Message {
+    Message::Response(Response {
+        id,
+        result: Some(result),
+        error: None,
+    })
+}
+
+// --- chunk0-1: per-request pending map ---
+
+#[tokio::test]
+async fn concurrent_requests_resolve_to_their_own_response() {
+    let transport = FakeTransport::new();
+    // No `initialize()` here, so bypass the init gate or the requests would never send.
+    let client = Arc::new(
+        ClientBuilder::new(transport.clone())
+            .skip_init_gate(true)
+            .build(),
+    );
+
+    let client_a = client.clone();
+    let task_a = tokio::spawn(async move { client_a.request("a", None).await });
+    let client_b = client.clone();
+    let task_b = tokio::spawn(async move { client_b.request("b", None).await });
+
+    // Let both requests register themselves in the pending map before replying, and
+    // reply out of order, to prove neither steals the other's response.
+    tokio::task::yield_now().await;
+    tokio::task::yield_now().await;
+
+    transport.push(ok_response(
+        RequestId::Number(2),
+        serde_json::json!("b-result"),
+    ));
+    transport.push(ok_response(
+        RequestId::Number(1),
+        serde_json::json!("a-result"),
+    ));
+
+    assert_eq!(
+        task_a.await.unwrap().unwrap(),
+        serde_json::json!("a-result")
+    );
+    assert_eq!(
+        task_b.await.unwrap().unwrap(),
+        serde_json::json!("b-result")
+    );
+}
+
+// --- chunk0-2: broadcast notification subscription ---
+
+#[tokio::test]
+async fn subscribe_receives_every_notification() {
+    let transport = FakeTransport::new();
+    let client = Client::new(transport.clone());
+    let mut sub = client.subscribe();
+
+    transport.push(Message::Notification(Notification::new(
+        "notifications/tools/list_changed",
+        None,
+    )));
+
+    let notif = sub.recv().await.unwrap();
+    assert_eq!(notif.method, "notifications/tools/list_changed");
+}
+
+#[tokio::test]
+async fn subscribe_method_filters_out_other_methods() {
+    let transport = FakeTransport::new();
+    let client = Client::new(transport.clone());
+    let mut filtered = Box::pin(client.subscribe_method("notifications/progress"));
+
+    transport.push(Message::Notification(Notification::new(
+        "notifications/tools/list_changed",
+        None,
+    )));
+    transport.push(Message::Notification(Notification::new(
+        "notifications/progress",
+        Some(serde_json::json!({ "progressToken": "1", "progress": 1.0 })),
+    )));
+
+    let notif = filtered.next().await.unwrap();
+    assert_eq!(notif.method, "notifications/progress");
+}
+
+fn is_cancelled_notification(message: &Message) -> bool {
+    matches!(message, Message::Notification(n) if n.method == "notifications/cancelled")
+}
+
+// --- chunk0-3: cancel-on-drop and configurable timeouts ---
+
+#[tokio::test]
+async fn cancel_on_drop_is_a_noop_once_resolved() {
+    let transport = FakeTransport::new();
+    let pending: Arc<Mutex<HashMap<RequestId, PendingResponse>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+    let id = RequestId::Number(1);
+
+    // Nothing registered for `id`: simulates the dispatcher having already removed it
+    // after resolving the real response before the guard ran.
+    drop(CancelOnDrop {
+        pending: pending.clone(),
+        transport: transport.clone(),
+        id,
+        resolved: false,
+    });
+
+    tokio::task::yield_now().await;
+    tokio::task::yield_now().await;
+
+    assert!(
+        transport.sent_messages().await.is_empty(),
+        "must not cancel a request that already completed"
+    );
+}
+
+#[tokio::test]
+async fn cancel_on_drop_notifies_server_while_still_pending() {
+    let transport = FakeTransport::new();
+    let pending: Arc<Mutex<HashMap<RequestId, PendingResponse>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+    let id = RequestId::Number(1);
+    let (tx, _rx) = tokio::sync::oneshot::channel();
+    pending.lock().await.insert(id.clone(), tx);
+
+    drop(CancelOnDrop {
+        pending: pending.clone(),
+        transport: transport.clone(),
+        id,
+        resolved: false,
+    });
+
+    tokio::task::yield_now().await;
+    tokio::task::yield_now().await;
+
+    assert!(pending.lock().await.is_empty());
+    let sent = transport.sent_messages().await;
+    assert!(sent.iter().any(is_cancelled_notification));
+}
+
+#[tokio::test]
+async fn timeout_cancels_and_notifies_server() {
+    let transport = FakeTransport::new();
+    let client = ClientBuilder::new(transport.clone())
+        .skip_init_gate(true)
+        .build();
+
+    let result = client
+        .request_with_timeout("slow", None, Duration::from_millis(20))
+        .await;
+    assert!(result.is_err());
+
+    tokio::task::yield_now().await;
+    tokio::task::yield_now().await;
+
+    let sent = transport.sent_messages().await;
+    assert!(sent.iter().any(is_cancelled_notification));
+}
+
+// --- chunk0-4: gate requests until initialization completes ---
+
+#[tokio::test]
+async fn request_queues_until_initialized_flag_is_set() {
+    let transport = FakeTransport::new();
+    let client = Arc::new(Client::new(transport.clone()));
+
+    let client_clone = client.clone();
+    let task = tokio::spawn(async move { client_clone.request("tools/list", None).await });
+
+    tokio::task::yield_now().await;
+    tokio::task::yield_now().await;
+    assert!(
+        transport.sent_messages().await.is_empty(),
+        "request must queue, not send, before initialization"
+    );
+
+    client
+        .initialized
+        .store(true, std::sync::atomic::Ordering::Release);
+    client.initialized_notify.notify_waiters();
+
+    tokio::task::yield_now().await;
+    transport.push(ok_response(RequestId::Number(1), serde_json::json!("ok")));
+    assert_eq!(task.await.unwrap().unwrap(), serde_json::json!("ok"));
+}
+
+#[tokio::test]
+async fn skip_init_gate_sends_immediately() {
+    let transport = FakeTransport::new();
+    let client = ClientBuilder::new(transport.clone())
+        .skip_init_gate(true)
+        .build();
+
+    let task = tokio::spawn(async move { client.request("tools/list", None).await });
+
+    tokio::task::yield_now().await;
+    tokio::task::yield_now().await;
+    assert_eq!(transport.sent_messages().await.len(), 1);
+
+    transport.push(ok_response(RequestId::Number(1), serde_json::json!("ok")));
+    assert_eq!(task.await.unwrap().unwrap(), serde_json::json!("ok"));
+}
+
+// --- chunk0-5: server-initiated requests via registered handlers ---
+
+#[tokio::test]
+async fn registered_handler_answers_server_request() {
+    let transport = FakeTransport::new();
+    let client = Client::new(transport.clone());
+
+    client
+        .on_request("roots/list", |_params| async move {
+            Ok(serde_json::json!({ "roots": [] }))
+        })
+        .await;
+
+    transport.push(Message::Request(Request::new(
+        "roots/list",
+        None,
+        RequestId::Number(99),
+    )));
+
+    // The handler runs on its own spawned task; give it a few turns to finish.
+    for _ in 0..4 {
+        tokio::task::yield_now().await;
+    }
+
+    let sent = transport.sent_messages().await;
+    let response = sent
+        .iter()
+        .find_map(|m| match m {
+            Message::Response(r) if r.id == RequestId::Number(99) => Some(r),
+            _ => None,
+        })
+        .expect("handler should have replied");
+
+    assert_eq!(response.result, Some(serde_json::json!({ "roots": [] })));
+    assert!(response.error.is_none());
+}
+
+#[tokio::test]
+async fn unregistered_method_gets_method_not_found_error() {
+    let transport = FakeTransport::new();
+    let _client = Client::new(transport.clone());
+
+    transport.push(Message::Request(Request::new(
+        "sampling/createMessage",
+        None,
+        RequestId::Number(7),
+    )));
+
+    for _ in 0..4 {
+        tokio::task::yield_now().await;
+    }
+
+    let sent = transport.sent_messages().await;
+    let response = sent
+        .iter()
+        .find_map(|m| match m {
+            Message::Response(r) if r.id == RequestId::Number(7) => Some(r),
+            _ => None,
+        })
+        .expect("should have replied with an error");
+
+    assert!(response.result.is_none());
+    assert!(response.error.is_some());
+}
+
+// --- chunk0-6: progress notifications correlated by token ---
+
+#[tokio::test]
+async fn progress_notifications_route_to_the_originating_request() {
+    let transport = FakeTransport::new();
+    let client = Arc::new(
+        ClientBuilder::new(transport.clone())
+            .skip_init_gate(true)
+            .build(),
+    );
+
+    let progress: Arc<std::sync::Mutex<Vec<ProgressNotification>>> =
+        Arc::new(std::sync::Mutex::new(Vec::new()));
+    let progress_clone = progress.clone();
+    let client_clone = client.clone();
+    let task = tokio::spawn(async move {
+        client_clone
+            .request_with_progress("tools/call", None, move |update| {
+                progress_clone.lock().unwrap().push(update);
+            })
+            .await
+    });
+
+    tokio::task::yield_now().await;
+
+    let sent = transport.sent_messages().await;
+    let Message::Request(req) = sent.first().expect("request should have been sent") else {
+        panic!("expected a request");
+    };
+    let token = req.params.as_ref().unwrap()["_meta"]["progressToken"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let request_id = req.id.clone();
+
+    // A progress update for our token is delivered to our callback...
+    transport.push(Message::Notification(Notification::new(
+        "notifications/progress",
+        Some(serde_json::json!({ "progressToken": token, "progress": 0.5, "total": 1.0 })),
+    )));
+    // ...while one for an unrelated token is ignored rather than misdelivered.
+    transport.push(Message::Notification(Notification::new(
+        "notifications/progress",
+        Some(serde_json::json!({ "progressToken": "not-ours", "progress": 0.1 })),
+    )));
+
+    tokio::task::yield_now().await;
+    tokio::task::yield_now().await;
+
+    transport.push(ok_response(
+        request_id,
+        serde_json::json!({ "content": [] }),
+    ));
+    task.await.unwrap().unwrap();
+
+    let updates = progress.lock().unwrap();
+    assert_eq!(updates.len(), 1);
+    assert_eq!(updates[0].progress, 0.5);
+}